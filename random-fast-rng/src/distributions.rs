@@ -0,0 +1,81 @@
+//! # Distributions
+//!
+//! Non-uniform sampling layered on top of any [`Random`](../trait.Random.html) source's
+//! uniform `f64` generation. Requires the `std` or `libm` feature, since `ln`/`sqrt` aren't
+//! available in bare `core`.
+
+use core::cell::Cell;
+use Random;
+
+#[cfg(feature = "libm")]
+extern crate libm;
+
+#[cfg(feature = "std")]
+fn ln(x: f64) -> f64 {
+    x.ln()
+}
+#[cfg(all(feature = "libm", not(feature = "std")))]
+fn ln(x: f64) -> f64 {
+    libm::log(x)
+}
+
+#[cfg(feature = "std")]
+fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+#[cfg(all(feature = "libm", not(feature = "std")))]
+fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+/// Samples `f64` values from an exponential distribution with rate `lambda`.
+pub struct Exp {
+    lambda: f64,
+}
+
+impl Exp {
+    /// Creates a new exponential distribution with the given rate `lambda`.
+    pub fn new(lambda: f64) -> Self {
+        Exp { lambda }
+    }
+
+    /// Draws a sample from this distribution using `rand` as the source of randomness, via
+    /// inverse-CDF sampling.
+    pub fn sample<R: Random + ?Sized>(&self, rand: &mut R) -> f64 {
+        let u: f64 = rand.gen();
+        -self.lambda.recip() * ln(1.0 - u)
+    }
+}
+
+/// Samples `f64` values from a normal (Gaussian) distribution via the polar Box–Muller method.
+pub struct Normal {
+    mean: f64,
+    std_dev: f64,
+    cached: Cell<Option<f64>>,
+}
+
+impl Normal {
+    /// Creates a new normal distribution with the given `mean` and standard deviation `std_dev`.
+    pub fn new(mean: f64, std_dev: f64) -> Self {
+        Normal { mean, std_dev, cached: Cell::new(None) }
+    }
+
+    /// Draws a sample from this distribution using `rand` as the source of randomness.
+    pub fn sample<R: Random + ?Sized>(&self, rand: &mut R) -> f64 {
+        if let Some(cached) = self.cached.take() {
+            return self.mean + self.std_dev * cached;
+        }
+
+        loop {
+            let x = 2.0 * rand.gen::<f64>() - 1.0;
+            let y = 2.0 * rand.gen::<f64>() - 1.0;
+            let s = x * x + y * y;
+            if s >= 1.0 || s == 0.0 {
+                continue;
+            }
+            let factor = sqrt(-2.0 * ln(s) / s);
+            self.cached.set(Some(y * factor));
+            return self.mean + self.std_dev * (x * factor);
+        }
+    }
+}
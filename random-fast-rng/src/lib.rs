@@ -6,7 +6,8 @@
 //!
 //! This crate provides a fast **non cryptographic** random number generator that implements the [`Random`](trait.Random.html) trait. <br>
 //! Currently it's implemented using the `Pcg32` algorithm, that generates 32 bit of random data for every state change. <br>
-//! the exact algorithm might change in the future, but the properties should stay the same (Blazing fast, non cryptographic, and minimal I/O)
+//! the exact algorithm might change in the future, but the properties should stay the same (Blazing fast, non cryptographic, and minimal I/O) <br>
+//! For use cases where raw speed matters more than `Pcg32`'s statistical quality, see [`XorShift64Plus`](struct.XorShift64Plus.html) (same 16 bytes of per-instance state).
 //! The crate is part of the `random-rs` facade, and as such supports older rust compilers(currently 1.13+) and should have only thin amount of dependencies.
 //!
 //! This Random generator is good for testing uses, and use cases that require some non-determinism. it shouldn't be used to generate keys/passwords. <br>
@@ -22,6 +23,19 @@ extern crate std;
 #[cfg(feature = "std")]
 mod thread;
 
+#[cfg(feature = "std")]
+mod seed;
+#[cfg(feature = "std")]
+pub use seed::{set_local_seed_source, SeedGenerator};
+
+#[cfg(any(feature = "std", feature = "libm"))]
+pub mod distributions;
+
+mod xorshift;
+pub use xorshift::XorShift64Plus;
+#[cfg(feature = "std")]
+pub use xorshift::{local_xorshift64, ThreadXorShift64Plus};
+
 pub extern crate random_trait;
 pub use random_trait::Random;
 
@@ -39,6 +53,40 @@ doc_comment::doctest!("../README.md");
 
 const PCG_DEFAULT_MULTIPLIER_64: u64 = 6_364_136_223_846_793_005;
 
+/// A 64-bit widening multiply for targets without a native `u128`, returning `(high, low)`.
+#[cfg(not(feature = "u128"))]
+fn widening_mul_u64(a: u64, b: u64) -> (u64, u64) {
+    let a_lo = a & 0xFFFF_FFFF;
+    let a_hi = a >> 32;
+    let b_lo = b & 0xFFFF_FFFF;
+    let b_hi = b >> 32;
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let cross = (lo_lo >> 32) + (hi_lo & 0xFFFF_FFFF) + (lo_hi & 0xFFFF_FFFF);
+    let lo = (lo_lo & 0xFFFF_FFFF) | (cross << 32);
+    let hi = hi_hi + (hi_lo >> 32) + (lo_hi >> 32) + (cross >> 32);
+    (hi, lo)
+}
+
+/// The internal stepping core shared by this crate's generators. Keeping the algorithm
+/// behind this small trait is what lets a backend's exact internals "change in the future"
+/// (as the crate docs warn) without disturbing the `Random` ergonomics built on top of it.
+trait FastCore {
+    /// Advances the state by one step and returns 32 bits of output.
+    fn next_u32(&mut self) -> u32;
+
+    /// Advances the state by two steps and returns 64 bits of output.
+    fn next_u64(&mut self) -> u64 {
+        let lo = u64::from(self.next_u32());
+        let hi = u64::from(self.next_u32());
+        (hi << 32) | lo
+    }
+}
+
 /// A FastRng struct implementing [`Random`](trait.Random.html). you can initialize it with your own seed using [`FastRng::seed()`](struct.FastRng.html#method.seed)
 /// Or if the `std` feature is enabled call [`FastRng::new()`](struct.FastRng.html#method.seed) which will seed it with the system time. <br>
 /// For ergonomics and ease of usability the Rng is also provided as a global thread local variable using [`local_rng()`](fn.local_rng.html)
@@ -80,16 +128,119 @@ impl FastRng {
         rng
     }
 
-    fn gen_u32(&mut self) -> u32 {
-        let old_state = self.state;
-        self.state = self.state.wrapping_mul(PCG_DEFAULT_MULTIPLIER_64).wrapping_add(self.inc);
+    /// Draws a uniform value of `T` in `[lo, hi)`, without modulo bias.
+    ///
+    /// # Panics
+    /// Panics if `lo >= hi`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use random_fast_rng::FastRng;
+    ///
+    /// let mut rng = FastRng::new();
+    /// let dice_roll: u32 = rng.gen_range(1, 7);
+    /// assert!(dice_roll >= 1 && dice_roll < 7);
+    /// ```
+    pub fn gen_range<T: SampleRange>(&mut self, lo: T, hi: T) -> T {
+        T::sample_range(self, lo, hi)
+    }
 
-        let xorshift = (((old_state >> 18) ^ old_state) >> 27) as u32;
-        let rot = (old_state >> 59) as i32;
-        (xorshift >> rot) | (xorshift << ((-rot) & 31))
+    // Draws a uniform `u64` in `[0, bound)` using Lemire's nearly-divisionless rejection
+    // method, which almost always needs only one draw.
+    #[cfg(feature = "u128")]
+    fn gen_range_u64(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return self.get_u64();
+        }
+        let mut x = self.get_u64();
+        let mut m = u128::from(x) * u128::from(bound);
+        let mut l = m as u64;
+        if l < bound {
+            let t = bound.wrapping_neg() % bound;
+            while l < t {
+                x = self.get_u64();
+                m = u128::from(x) * u128::from(bound);
+                l = m as u64;
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    // Draws a uniform `u64` in `[0, bound)` using Lemire's nearly-divisionless rejection
+    // method, which almost always needs only one draw.
+    #[cfg(not(feature = "u128"))]
+    fn gen_range_u64(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return self.get_u64();
+        }
+        let mut x = self.get_u64();
+        let (mut hi, mut lo) = widening_mul_u64(x, bound);
+        if lo < bound {
+            let t = bound.wrapping_neg() % bound;
+            while lo < t {
+                x = self.get_u64();
+                let (new_hi, new_lo) = widening_mul_u64(x, bound);
+                hi = new_hi;
+                lo = new_lo;
+            }
+        }
+        hi
     }
+
+    /// Advances the generator's state as if `delta` steps had been taken, in `O(log delta)`
+    /// time via binary exponentiation of the underlying LCG step. Lets independent workers
+    /// deterministically partition one seeded stream without replaying it.
+    pub fn advance(&mut self, mut delta: u64) {
+        let mut acc_mult: u64 = 1;
+        let mut acc_plus: u64 = 0;
+        let mut cur_mult = PCG_DEFAULT_MULTIPLIER_64;
+        let mut cur_plus = self.inc;
+        while delta > 0 {
+            if delta & 1 == 1 {
+                acc_mult = acc_mult.wrapping_mul(cur_mult);
+                acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            }
+            cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+            delta >>= 1;
+        }
+        self.state = self.state.wrapping_mul(acc_mult).wrapping_add(acc_plus);
+    }
+
+    /// Derives an independent child generator on a distinct stream, so e.g. parallel
+    /// workers can each get a non-overlapping sequence from one seeded parent.
+    pub fn split(&mut self) -> FastRng {
+        let seed = self.get_u64();
+        let seq = self.get_u64();
+        FastRng::seed(seed, seq)
+    }
+}
+
+/// Types [`FastRng::gen_range`](struct.FastRng.html#method.gen_range) can draw uniformly
+/// from a `[lo, hi)` range.
+pub trait SampleRange: Sized {
+    /// Draws a uniform value in `[lo, hi)`.
+    fn sample_range(rng: &mut FastRng, lo: Self, hi: Self) -> Self;
 }
 
+macro_rules! impl_sample_range {
+    ($ty:ty) => {
+        impl SampleRange for $ty {
+            fn sample_range(rng: &mut FastRng, lo: Self, hi: Self) -> Self {
+                assert!(lo < hi, "FastRng::gen_range requires lo < hi");
+                let span = (hi - lo) as u64;
+                lo.wrapping_add(rng.gen_range_u64(span) as Self)
+            }
+        }
+    };
+}
+
+impl_sample_range! {u8}
+impl_sample_range! {u16}
+impl_sample_range! {u32}
+impl_sample_range! {u64}
+impl_sample_range! {usize}
+
 /// Returns a thread local instance which is seeded only once per thread (no need to worry about dropping and reinitializing)
 ///
 /// # Examples
@@ -104,12 +255,20 @@ impl FastRng {
 pub fn local_rng() -> ThreadFastRng {
     use std::cell::RefCell;
     thread_local! {
-        pub static THREAD_FAST_RNG: RefCell<FastRng> = RefCell::new(FastRng::new());
+        pub static THREAD_FAST_RNG: RefCell<FastRng> = RefCell::new(new_local_rng());
     }
     let ptr = THREAD_FAST_RNG.with(|r| r.as_ptr());
     ThreadFastRng::from_ptr(ptr)
 }
 
+#[cfg(feature = "std")]
+fn new_local_rng() -> FastRng {
+    match seed::next_local_seed() {
+        Some((seed, seq)) => FastRng::seed(seed, seq),
+        None => FastRng::new(),
+    }
+}
+
 #[cfg(feature = "std")]
 fn time_seed() -> (u64, u64) {
     use std::time;
@@ -119,19 +278,30 @@ fn time_seed() -> (u64, u64) {
     (unix.as_secs(), u64::from(unix.subsec_nanos()))
 }
 
+impl FastCore for FastRng {
+    fn next_u32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.state = self.state.wrapping_mul(PCG_DEFAULT_MULTIPLIER_64).wrapping_add(self.inc);
+
+        let xorshift = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rot = (old_state >> 59) as i32;
+        (xorshift >> rot) | (xorshift << ((-rot) & 31))
+    }
+}
+
 impl Random for FastRng {
     type Error = ();
 
     fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
         for chunk in buf.chunks_mut(4) {
-            let rand: [u8; 4] = unsafe { mem::transmute(self.gen_u32()) };
+            let rand: [u8; 4] = unsafe { mem::transmute(self.next_u32()) };
             let len = chunk.len();
             chunk.copy_from_slice(&rand[..len]);
         }
         Ok(())
     }
     fn get_u32(&mut self) -> u32 {
-        self.gen_u32()
+        self.next_u32()
     }
 }
 
@@ -158,4 +328,40 @@ mod tests {
         let f: f64 = rng.gen();
         assert!(f > 0.0 && f < 1.0);
     }
+
+    #[test]
+    fn test_gen_range() {
+        let mut rng = FastRng::new();
+        for _ in 0..100 {
+            let n: u32 = rng.gen_range(1, 7);
+            assert!(n >= 1 && n < 7);
+            let n: u64 = rng.gen_range(0, 1);
+            assert_eq!(n, 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "FastRng::gen_range requires lo < hi")]
+    fn test_gen_range_empty_panics() {
+        let mut rng = FastRng::new();
+        let _: u32 = rng.gen_range(5, 5);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut stepped = FastRng::seed(1, 2);
+        for _ in 0..5 {
+            stepped.get_u32();
+        }
+        let mut advanced = FastRng::seed(1, 2);
+        advanced.advance(5);
+        assert_eq!(stepped.get_u32(), advanced.get_u32());
+    }
+
+    #[test]
+    fn test_split() {
+        let mut rng = FastRng::seed(1, 2);
+        let mut child = rng.split();
+        assert_ne!(rng.get_u64(), child.get_u64());
+    }
 }
@@ -0,0 +1,142 @@
+//! # XorShift64+
+//!
+//! An alternative backend to [`FastRng`](../struct.FastRng.html), for when xorshift64+'s
+//! speed matters more than PCG's statistical quality. Same per-instance footprint (two
+//! `u64` words of state) as `FastRng`.
+
+use core::mem;
+
+use FastCore;
+use Random;
+#[cfg(feature = "std")]
+use time_seed;
+#[cfg(feature = "std")]
+use thread::FromRawPtr;
+
+/// A non-cryptographic generator implementing the xorshift64+ scheme. Shares `FastRng`'s
+/// `seed`/`new`/`local_rng`-style ergonomics, and the same 16 bytes of per-instance state.
+pub struct XorShift64Plus {
+    one: u64,
+    two: u64,
+}
+
+impl XorShift64Plus {
+    /// Creates a new instance seeded with the system time.
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        let (a, b) = time_seed();
+        Self::seed(a, b)
+    }
+
+    /// Manually seeds the generator in `no-std` cases. Both words should ideally be random;
+    /// an all-zero state is avoided (it would otherwise produce nothing but zeroes), so a
+    /// zero input word is replaced with `1`.
+    pub fn seed(one: u64, two: u64) -> Self {
+        XorShift64Plus { one: if one == 0 { 1 } else { one }, two: if two == 0 { 1 } else { two } }
+    }
+}
+
+impl FastCore for XorShift64Plus {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut s1 = self.one;
+        let s0 = self.two;
+        let result = s1.wrapping_add(s0);
+        s1 ^= s1 << 17;
+        s1 ^= s1 >> 7;
+        s1 ^= s0;
+        s1 ^= s0 >> 16;
+        self.one = s0;
+        self.two = s1;
+        result
+    }
+}
+
+impl Random for XorShift64Plus {
+    type Error = ();
+
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+        for chunk in buf.chunks_mut(8) {
+            let rand: [u8; 8] = unsafe { mem::transmute(self.next_u64()) };
+            let len = chunk.len();
+            chunk.copy_from_slice(&rand[..len]);
+        }
+        Ok(())
+    }
+    fn get_u64(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
+
+#[cfg(feature = "std")]
+mod thread {
+    use super::XorShift64Plus;
+
+    use core::ops::{Deref, DerefMut};
+
+    /// A shim that points to the global `XorShift64Plus` instance. Mirrors
+    /// [`ThreadFastRng`](../struct.ThreadFastRng.html).
+    pub struct ThreadXorShift64Plus(*mut XorShift64Plus);
+
+    impl Deref for ThreadXorShift64Plus {
+        type Target = XorShift64Plus;
+
+        fn deref(&self) -> &Self::Target {
+            unsafe { &*self.0 }
+        }
+    }
+
+    impl DerefMut for ThreadXorShift64Plus {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            unsafe { &mut *self.0 }
+        }
+    }
+
+    impl super::FromRawPtr<XorShift64Plus> for ThreadXorShift64Plus {
+        fn from_ptr(ptr: *mut XorShift64Plus) -> ThreadXorShift64Plus {
+            ThreadXorShift64Plus(ptr)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::thread::ThreadXorShift64Plus;
+
+/// Returns a thread-local `XorShift64Plus`, seeded only once per thread. Mirrors
+/// [`local_rng()`](../fn.local_rng.html), but for the xorshift64+ backend.
+#[cfg(feature = "std")]
+pub fn local_xorshift64() -> ThreadXorShift64Plus {
+    use std::cell::RefCell;
+    thread_local! {
+        pub static THREAD_XORSHIFT: RefCell<XorShift64Plus> = RefCell::new(XorShift64Plus::new());
+    }
+    let ptr = THREAD_XORSHIFT.with(|r| r.as_ptr());
+    ThreadXorShift64Plus::from_ptr(ptr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local() {
+        let mut local_rng = local_xorshift64();
+        let a: u64 = local_rng.gen();
+        let b: u32 = local_rng.gen();
+        let c: [u8; 64] = local_rng.gen();
+        assert_ne!(a, 0);
+        assert_ne!(b, 0);
+        assert_ne!(&c[..], &[0u8; 64][..]);
+    }
+
+    #[test]
+    fn test_distinct_from_seed() {
+        let mut rng = XorShift64Plus::seed(1, 2);
+        let a = rng.get_u64();
+        let b = rng.get_u64();
+        assert_ne!(a, b);
+    }
+}
@@ -229,82 +229,32 @@ impl GenerateRand for bool {
     }
 }
 
-// Source: https://mumble.net/~campbell/2014/04/28/uniform-random-float
-// https://mumble.net/~campbell/2014/04/28/random_real.c
+// Source: Saito & Matsumoto's mantissa-bit construction (no FP divide; the mantissa bits
+// are taken from the draw with a mask rather than a shift).
 impl GenerateRand for f64 {
     fn generate<R: Random + ?Sized>(rand: &mut R) -> Self {
-        let mut exponent: i32 = -64;
-        let mut significand = rand.get_u64();
-        while significand == 0 {
-            exponent -= 64;
-            if exponent < -1074i32 {
-                // E min(-1022)-p(53)+1  (https://en.wikipedia.org/wiki/IEEE_754)
-                // In reallity this should probably never happen. prob of ~1/(2^1024) unless randomness is broken.
-                unreachable!("The randomness is broken, got 0 16 times. (prob of 1/2^1024)");
-            }
-            significand = rand.get_u64();
-        }
-
-        // Shift the leading zeros into the exponent
-        let shift = significand.leading_zeros() as i32;
-        if shift > 0 {
-            exponent -= shift;
-            significand <<= shift;
-            significand |= rand.get_u64() >> (64 - shift);
-        }
-        // Set the sticky bit.
-        significand |= 1;
-
-        // Convert to float and scale by 2^exponent.
-        significand as f64 * exp2(exponent)
+        const ONE_BITS: u64 = 0x3ff0_0000_0000_0000;
+        let mantissa = rand.get_u64() & 0x000f_ffff_ffff_ffff; // 52 random mantissa bits
+        let bits = ONE_BITS | mantissa;
+        // bits now represents a value uniform in [1.0, 2.0); shift it down to [0.0, 1.0).
+        let value: f64 = unsafe { mem::transmute(bits) };
+        value - 1.0
     }
 }
 
-// Source: https://mumble.net/~campbell/2014/04/28/uniform-random-float
-// https://mumble.net/~campbell/2014/04/28/random_real.c
+// Source: Saito & Matsumoto's mantissa-bit construction (no FP divide; the mantissa bits
+// are taken from the draw with a mask rather than a shift).
 impl GenerateRand for f32 {
     fn generate<R: Random + ?Sized>(rand: &mut R) -> Self {
-        let mut exponent: i32 = -32;
-        let mut significand = rand.get_u32();
-        while significand == 0 {
-            exponent -= 32;
-            if exponent < -149i32 {
-                // E min(-126)-p(24)+1  (https://en.wikipedia.org/wiki/IEEE_754)
-                // In reallity this should probably never happen. prob of ~1/(2^1024) unless randomness is broken.
-                unreachable!("The randomness is broken, got 0 5 times. (prob of 1/2^160)");
-                // TODO: Should this stay unreachable or change to return 0?
-            }
-            significand = rand.get_u32();
-        }
-
-        // Shift the leading zeros into the exponent
-        let shift = significand.leading_zeros() as i32;
-        if shift != 0 {
-            exponent -= shift;
-            significand <<= shift;
-            significand |= rand.get_u32() >> (32 - shift);
-        }
-        // Set the sticky bit, almost definitely another 1 in the random stream.
-        significand |= 1;
-
-        // Convert to float and scale by 2^exponent.
-        significand as f32 * exp2f(exponent)
+        const ONE_BITS: u32 = 0x3f80_0000;
+        let mantissa = rand.get_u32() & 0x007f_ffff; // 23 random mantissa bits
+        let bits = ONE_BITS | mantissa;
+        // bits now represents a value uniform in [1.0, 2.0); shift it down to [0.0, 1.0).
+        let value: f32 = unsafe { mem::transmute(bits) };
+        value - 1.0
     }
 }
 
-/// This is from IEEE-754.
-/// you take the E max, subtract the exponent from it, and shift it according to the precision-1
-fn exp2f(exp: i32) -> f32 {
-    debug_assert!(exp > -127);
-    let bits = ((127i32 + exp) as u32) << 23u32;
-    unsafe { mem::transmute(bits) } // this is the same as `f32::from_bits`
-}
-fn exp2(exp: i32) -> f64 {
-    debug_assert!(exp > -1023);
-    let bits = ((1023i32 + exp) as u64) << 52u64;
-    unsafe { mem::transmute(bits) } // this is the same as `f64::from_bits`
-}
-
 // Will overflow(i.e. sign extend) correctly https://doc.rust-lang.org/nomicon/casts.html.
 // should only be used with the same type.
 macro_rules! impl_generate_rand_ifromu {
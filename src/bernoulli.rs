@@ -0,0 +1,116 @@
+//! # Bernoulli
+//!
+//! A biased coin flip with an exact probability, either from an `f64` or an exact
+//! `numerator/denominator` ratio.
+
+use Random;
+
+/// A boolean distribution returning `true` with a configurable probability.
+pub struct Bernoulli {
+    // Fixed-point probability scaled by 2^64: sampling draws a uniform u64 `x` and
+    // returns `x < threshold`.
+    threshold: u64,
+    // `threshold = u64::max_value()` can't represent probability 1 exactly (`x < threshold`
+    // is still false for the 1-in-2^64 draw of `x == u64::max_value()`), so `p == 1.0` is
+    // tracked separately to make it truly unconditional.
+    always_true: bool,
+}
+
+impl Bernoulli {
+    /// Creates a `Bernoulli` that returns `true` with probability `p`, where `p` is in `[0, 1]`.
+    pub fn new(p: f64) -> Self {
+        debug_assert!(p >= 0.0 && p <= 1.0);
+        if p >= 1.0 {
+            return Bernoulli { threshold: u64::max_value(), always_true: true };
+        }
+        Bernoulli { threshold: (p * 18_446_744_073_709_551_616.0) as u64, always_true: false }
+    }
+
+    /// Creates a `Bernoulli` that returns `true` with probability `numerator/denominator`,
+    /// exactly (no floating-point rounding), for `denominator` up to the word size.
+    #[cfg(feature = "u128")]
+    pub fn from_ratio(numerator: u64, denominator: u64) -> Self {
+        debug_assert!(denominator > 0 && numerator <= denominator);
+        if numerator == denominator {
+            return Bernoulli { threshold: u64::max_value(), always_true: true };
+        }
+        let threshold = ((u128::from(numerator) << 64) / u128::from(denominator)) as u64;
+        Bernoulli { threshold, always_true: false }
+    }
+
+    /// Creates a `Bernoulli` that returns `true` with probability `numerator/denominator`,
+    /// exactly (no floating-point rounding), for `denominator` up to the word size.
+    #[cfg(not(feature = "u128"))]
+    pub fn from_ratio(numerator: u64, denominator: u64) -> Self {
+        debug_assert!(denominator > 0 && numerator <= denominator);
+        if numerator == denominator {
+            return Bernoulli { threshold: u64::max_value(), always_true: true };
+        }
+        Bernoulli { threshold: div_u64_shl64(numerator, denominator), always_true: false }
+    }
+
+    /// Draws a sample from this distribution using `rand` as the source of randomness.
+    pub fn sample<R: Random + ?Sized>(&self, rand: &mut R) -> bool {
+        self.always_true || rand.get_u64() < self.threshold
+    }
+}
+
+/// Computes `floor((numerator << 64) / denominator)` without a native 128-bit type,
+/// assuming `numerator < denominator` (so the result fits in a `u64`).
+#[cfg(not(feature = "u128"))]
+fn div_u64_shl64(numerator: u64, denominator: u64) -> u64 {
+    let mut remainder: u64 = numerator;
+    let mut quotient: u64 = 0;
+    for _ in 0..64 {
+        let carry = remainder >> 63;
+        remainder <<= 1;
+        quotient <<= 1;
+        if carry != 0 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Error;
+
+    // Always returns the maximum possible draw, the one case `threshold = u64::max_value()`
+    // alone fails to cover for a `p == 1.0` distribution.
+    struct MaxRng;
+
+    impl Random for MaxRng {
+        fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+            for b in buf.iter_mut() {
+                *b = 0xff;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_p_one_is_unconditional() {
+        let always = Bernoulli::new(1.0);
+        let mut rng = MaxRng;
+        for _ in 0..10 {
+            assert!(always.sample(&mut rng));
+        }
+    }
+
+    #[test]
+    fn test_p_zero_is_never_true() {
+        let never = Bernoulli::new(0.0);
+        let mut rng = MaxRng;
+        assert!(!never.sample(&mut rng));
+    }
+
+    #[test]
+    fn test_from_ratio_equal_is_unconditional() {
+        let always = Bernoulli::from_ratio(5, 5);
+        let mut rng = MaxRng;
+        assert!(always.sample(&mut rng));
+    }
+}
@@ -0,0 +1,106 @@
+//! # Distributions
+//!
+//! This module provides non-uniform sampling on top of the uniform values produced by
+//! any [`Random`](../trait.Random.html) source. <br>
+//! Requires the `std` or `libm` feature, since `ln`/`sqrt` aren't available in bare `core`.
+
+use core::cell::Cell;
+use Random;
+
+#[cfg(feature = "libm")]
+extern crate libm;
+
+#[cfg(feature = "std")]
+fn ln(x: f64) -> f64 {
+    x.ln()
+}
+#[cfg(all(feature = "libm", not(feature = "std")))]
+fn ln(x: f64) -> f64 {
+    libm::log(x)
+}
+
+#[cfg(feature = "std")]
+fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+#[cfg(all(feature = "libm", not(feature = "std")))]
+fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+/// Samples `f64` values from an exponential distribution with rate `lambda`.
+pub struct Exp {
+    lambda: f64,
+}
+
+impl Exp {
+    /// Creates a new exponential distribution with the given rate `lambda`.
+    pub fn new(lambda: f64) -> Self {
+        Exp { lambda }
+    }
+
+    /// Draws a sample from this distribution using `rand` as the source of randomness.
+    pub fn sample<R: Random + ?Sized>(&self, rand: &mut R) -> f64 {
+        // `gen::<f64>()` is in `(0, 1]`; the clamp keeps `ln` away from 0 regardless.
+        let u = rand.gen::<f64>().max(core::f64::MIN_POSITIVE);
+        -ln(u) / self.lambda
+    }
+}
+
+/// Samples `f64` values from a normal (Gaussian) distribution via the polar Box–Muller method.
+pub struct Normal {
+    mean: f64,
+    std_dev: f64,
+    cached: Cell<Option<f64>>,
+}
+
+impl Normal {
+    /// Creates a new normal distribution with the given `mean` and standard deviation `std_dev`.
+    pub fn new(mean: f64, std_dev: f64) -> Self {
+        Normal { mean, std_dev, cached: Cell::new(None) }
+    }
+
+    /// Draws a sample from this distribution using `rand` as the source of randomness.
+    pub fn sample<R: Random + ?Sized>(&self, rand: &mut R) -> f64 {
+        if let Some(cached) = self.cached.take() {
+            return self.mean + self.std_dev * cached;
+        }
+
+        loop {
+            let u = 2.0 * rand.gen::<f64>() - 1.0;
+            let v = 2.0 * rand.gen::<f64>() - 1.0;
+            let s = u * u + v * v;
+            if s >= 1.0 || s == 0.0 {
+                continue;
+            }
+            let factor = sqrt(-2.0 * ln(s) / s);
+            self.cached.set(Some(v * factor));
+            return self.mean + self.std_dev * (u * factor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fast::FastRng;
+
+    #[test]
+    fn test_exp_is_non_negative() {
+        let exp = Exp::new(1.5);
+        let mut rng = FastRng::seed(1, 2);
+        for _ in 0..100 {
+            assert!(exp.sample(&mut rng) >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_normal_is_centered_near_mean() {
+        let normal = Normal::new(10.0, 2.0);
+        let mut rng = FastRng::seed(1, 2);
+        let samples = 1000;
+        let sum: f64 = (0..samples).map(|_| normal.sample(&mut rng)).sum();
+        let mean = sum / f64::from(samples);
+        assert!((mean - 10.0).abs() < 1.0);
+    }
+}
@@ -11,9 +11,12 @@
 //!
 
 use core::mem;
-use Random;
+use {Error, Random};
+use reseeding::Seedable;
 
 const PCG_DEFAULT_MULTIPLIER_64: u64 = 6_364_136_223_846_793_005;
+#[cfg(feature = "u128")]
+const PCG_DEFAULT_MULTIPLIER_128: u128 = 0x2360_ed05_1fc6_5da4_4385_df64_9fcc_f645;
 
 /// A FastRng struct implementing [`Random`](trait.Random.html). you can initialize it with your own seed using [`FastRng::seed()`](struct.FastRng.html#method.seed)
 /// Or if the `std` feature is enabled call [`FastRng::new()`](struct.FastRng.html#method.seed) which will seed it with the system time. <br>
@@ -52,7 +55,7 @@ impl FastRng {
     /// as this is firstly a fast random generator, not a PCG random generator, and not a deterministic random generator.
     pub fn seed(seed: u64, seq: u64) -> Self {
         let init_inc = (seq << 1) | 1;
-        let init_state = seed + init_inc;
+        let init_state = seed.wrapping_add(init_inc);
         let mut rng = FastRng { state: init_state, inc: init_inc };
         rng.state = rng.state.wrapping_mul(PCG_DEFAULT_MULTIPLIER_64).wrapping_add(rng.inc);
         rng
@@ -68,6 +71,65 @@ impl FastRng {
     }
 }
 
+/// A 64-bit-output companion to [`FastRng`](struct.FastRng.html), implementing the PCG
+/// 128/64 "DXSM" output function over a 128-bit LCG state. <br>
+/// Where `FastRng` needs two steps to fill a `u64`, this generator produces a full `u64`
+/// per step, which makes `get_u64`/`get_u128` and bulk `try_fill_bytes` cheaper. <br>
+/// Gated on the `u128` feature since the state is a `u128`.
+#[cfg(feature = "u128")]
+pub struct FastRng64 {
+    state: u128,
+    inc: u128,
+}
+
+#[cfg(feature = "u128")]
+impl FastRng64 {
+    /// Creates a new instance of `FastRng64` seeded with the system time.
+    #[cfg(feature = "std")]
+    pub fn new() -> Self {
+        let (a, b) = time_seed();
+        Self::seed(u128::from(a), u128::from(b))
+    }
+
+    /// A function to manually seed the Rng in `no-std` cases, mirroring
+    /// [`FastRng::seed`](struct.FastRng.html#method.seed).
+    pub fn seed(seed: u128, seq: u128) -> Self {
+        let init_inc = (seq << 1) | 1;
+        let init_state = seed.wrapping_add(init_inc);
+        let mut rng = FastRng64 { state: init_state, inc: init_inc };
+        rng.state = rng.state.wrapping_mul(PCG_DEFAULT_MULTIPLIER_128).wrapping_add(rng.inc);
+        rng
+    }
+
+    fn gen_u64(&mut self) -> u64 {
+        let old_state = self.state;
+        self.state = self.state.wrapping_mul(PCG_DEFAULT_MULTIPLIER_128).wrapping_add(self.inc);
+
+        let mut hi = (old_state >> 64) as u64;
+        let lo = old_state as u64;
+        hi ^= hi >> 32;
+        hi = hi.wrapping_mul(PCG_DEFAULT_MULTIPLIER_64);
+        hi ^= hi >> 48;
+        hi.wrapping_mul(lo | 1)
+    }
+}
+
+#[cfg(feature = "u128")]
+impl Random for FastRng64 {
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        for chunk in buf.chunks_mut(8) {
+            let rand: [u8; 8] = unsafe { mem::transmute(self.gen_u64()) };
+            let len = chunk.len();
+            chunk.copy_from_slice(&rand[..len]);
+        }
+        Ok(())
+    }
+
+    fn get_u64(&mut self) -> u64 {
+        self.gen_u64()
+    }
+}
+
 /// Returns a thread local instance which is seeded only once per thread (no need to worry about dropping and reinitializing)
 ///
 /// # Examples
@@ -99,9 +161,7 @@ fn time_seed() -> (u64, u64) {
 }
 
 impl Random for FastRng {
-    type Error = ();
-
-    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Self::Error> {
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Error> {
         for chunk in buf.chunks_mut(4) {
             let rand: [u8; 4] = unsafe { mem::transmute(self.gen_u32()) };
             let len = chunk.len();
@@ -114,6 +174,12 @@ impl Random for FastRng {
     }
 }
 
+impl Seedable for FastRng {
+    fn seed(seed: u64, seq: u64) -> Self {
+        FastRng::seed(seed, seq)
+    }
+}
+
 #[cfg(feature = "std")]
 mod thread {
     use super::FastRng;
@@ -179,4 +245,16 @@ mod tests {
         let f: f64 = rng.gen();
         assert!(f > 0.0 && f < 1.0);
     }
+
+    #[cfg(feature = "u128")]
+    #[test]
+    fn test_fast_rng64() {
+        let mut rng = FastRng64::new();
+        let a: u64 = rng.gen();
+        let b: u64 = rng.gen();
+        assert_ne!(a, 0);
+        assert_ne!(a, b);
+        let c: [u8; 64] = rng.gen();
+        assert_ne!(&c[..], &[0u8; 64][..]);
+    }
 }
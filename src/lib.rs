@@ -1,6 +1,26 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod fast;
+
+#[cfg(any(feature = "std", feature = "libm"))]
+pub mod distributions;
+
+#[cfg(feature = "alloc")]
+pub mod weighted;
+
+pub mod reseeding;
+
+pub mod bernoulli;
+
 use core::{char, mem};
+use core::ops::{Range, RangeInclusive};
 
 pub enum Error {
     Something,
@@ -54,14 +74,166 @@ pub trait Random {
         unsafe { mem::transmute(buf) }
     }
 
-    // TODO: More research, least/most significant bit?
     fn get_bool(&mut self) -> bool {
-        let bit = self.get_u8() & 0b1000_0000;
-        debug_assert!(bit < 2);
-        bit == 1
+        self.get_u8() & 1 == 1
+    }
+
+    /// Returns a uniform `u32` in `[0, bound)` without modulo bias, using Lemire's
+    /// nearly-divisionless rejection method. `bound == 0` is treated as the full `u32` range.
+    fn gen_range_u32(&mut self, bound: u32) -> u32 {
+        if bound == 0 {
+            return self.get_u32();
+        }
+        let mut x = self.get_u32();
+        let mut m = u64::from(x) * u64::from(bound);
+        let mut l = m as u32;
+        if l < bound {
+            let t = (0u32.wrapping_sub(bound)) % bound;
+            while l < t {
+                x = self.get_u32();
+                m = u64::from(x) * u64::from(bound);
+                l = m as u32;
+            }
+        }
+        (m >> 32) as u32
+    }
+
+    /// Returns a uniform `u64` in `[0, bound)` without modulo bias, using Lemire's
+    /// nearly-divisionless rejection method. `bound == 0` is treated as the full `u64` range.
+    #[cfg(feature = "u128")]
+    fn gen_range_u64(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return self.get_u64();
+        }
+        let mut x = self.get_u64();
+        let mut m = u128::from(x) * u128::from(bound);
+        let mut l = m as u64;
+        if l < bound {
+            let t = (0u64.wrapping_sub(bound)) % bound;
+            while l < t {
+                x = self.get_u64();
+                m = u128::from(x) * u128::from(bound);
+                l = m as u64;
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    /// Returns a uniform `u64` in `[0, bound)` without modulo bias, using Lemire's
+    /// nearly-divisionless rejection method. `bound == 0` is treated as the full `u64` range.
+    #[cfg(not(feature = "u128"))]
+    fn gen_range_u64(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return self.get_u64();
+        }
+        let mut x = self.get_u64();
+        let (mut hi, mut lo) = widening_mul_u64(x, bound);
+        if lo < bound {
+            let t = (0u64.wrapping_sub(bound)) % bound;
+            while lo < t {
+                x = self.get_u64();
+                let (new_hi, new_lo) = widening_mul_u64(x, bound);
+                hi = new_hi;
+                lo = new_lo;
+            }
+        }
+        hi
+    }
+
+    /// Returns a uniform value of `T` drawn from `range`, without modulo bias.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty (`range.start >= range.end`).
+    fn gen_range<T: SampleUniform>(&mut self, range: Range<T>) -> T {
+        T::sample_range(self, range.start, range.end, false)
+    }
+
+    /// Returns a uniform value of `T` drawn from `range` (inclusive of both ends), without modulo bias.
+    fn gen_range_inclusive<T: SampleUniform>(&mut self, range: RangeInclusive<T>) -> T {
+        let (low, high) = range.into_inner();
+        T::sample_range(self, low, high, true)
+    }
+
+    /// Shuffles `slice` in place using the Fisher–Yates algorithm.
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        let mut i = slice.len();
+        while i > 1 {
+            i -= 1;
+            let j = self.gen_range_u32(i as u32 + 1) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Returns a uniformly random reference into `slice`, or `None` if it's empty.
+    fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let idx = self.gen_range_u32(slice.len() as u32) as usize;
+        slice.get(idx)
+    }
+
+    /// Returns a uniformly random item from `iter` using reservoir sampling, so it works
+    /// in a single pass over an iterator of unknown length and in constant memory.
+    fn choose_iter<I: Iterator>(&mut self, mut iter: I) -> Option<I::Item> {
+        let mut candidate = iter.next()?;
+        let mut k: u32 = 1;
+        for item in iter {
+            k += 1;
+            if self.gen_range_u32(k) == 0 {
+                candidate = item;
+            }
+        }
+        Some(candidate)
     }
 }
 
+/// A 64-bit widening multiply for targets without a native `u128`, returning `(high, low)`.
+#[cfg(not(feature = "u128"))]
+fn widening_mul_u64(a: u64, b: u64) -> (u64, u64) {
+    let a_lo = a & 0xFFFF_FFFF;
+    let a_hi = a >> 32;
+    let b_lo = b & 0xFFFF_FFFF;
+    let b_hi = b >> 32;
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let cross = (lo_lo >> 32) + (hi_lo & 0xFFFF_FFFF) + (lo_hi & 0xFFFF_FFFF);
+    let lo = (lo_lo & 0xFFFF_FFFF) | (cross << 32);
+    let hi = hi_hi + (hi_lo >> 32) + (lo_hi >> 32) + (cross >> 32);
+    (hi, lo)
+}
+
+/// Types that can be drawn uniformly from a bounded range without modulo bias.
+/// See [`Random::gen_range`](trait.Random.html#method.gen_range) and
+/// [`Random::gen_range_inclusive`](trait.Random.html#method.gen_range_inclusive).
+pub trait SampleUniform: Sized {
+    /// Draws a uniform value in `[low, high)`, or `[low, high]` when `inclusive` is set.
+    fn sample_range<R: Random + ?Sized>(rand: &mut R, low: Self, high: Self, inclusive: bool) -> Self;
+}
+
+macro_rules! impl_sample_uniform {
+    ($ty:ty, $gen_range:ident) => {
+        impl SampleUniform for $ty {
+            fn sample_range<R: Random + ?Sized>(rand: &mut R, low: Self, high: Self, inclusive: bool) -> Self {
+                let span = if inclusive {
+                    high.wrapping_sub(low).wrapping_add(1)
+                } else {
+                    assert!(low < high, "cannot sample an empty range");
+                    high - low
+                };
+                low.wrapping_add(rand.$gen_range(span))
+            }
+        }
+    };
+}
+
+impl_sample_uniform! {u32, gen_range_u32}
+impl_sample_uniform! {u64, gen_range_u64}
+
 
 // Will overflow(i.e. sign extend) correctly https://doc.rust-lang.org/nomicon/casts.html.
 // should only be used with the same type.
@@ -152,7 +324,20 @@ impl GenerateRand for f64 {
         significand |= 1;
 
         // Convert to float and scale by 2^exponent.
-        significand as f64 * f64::from(1 << exponent)
+        significand as f64 * pow2_f64(exponent)
+    }
+}
+
+// `1 << exponent` overflow-panics for every negative `exponent` this algorithm produces
+// (it's always <= -64), so 2^exponent is built directly from the IEEE-754 bit pattern
+// instead. Subnormal results (`exponent < -1022`) can't be encoded in a single biased
+// exponent field, so those are split into two in-range multiplies.
+fn pow2_f64(exponent: i32) -> f64 {
+    if exponent >= -1022 {
+        f64::from_bits(((exponent + 1023) as u64) << 52)
+    } else {
+        let lo = f64::from_bits((1u64) << 52); // 2^-1022
+        lo * pow2_f64(exponent + 1022)
     }
 }
 
@@ -183,7 +368,20 @@ impl GenerateRand for f32 {
         significand |= 1;
 
         // Convert to float and scale by 2^exponent.
-        significand as f32 * f32::from(1i16 << exponent)
+        significand as f32 * pow2_f32(exponent)
+    }
+}
+
+// `1i16 << exponent` overflow-panics for every negative `exponent` this algorithm produces
+// (it's always <= -32), so 2^exponent is built directly from the IEEE-754 bit pattern
+// instead. Subnormal results (`exponent < -126`) can't be encoded in a single biased
+// exponent field, so those are split into two in-range multiplies.
+fn pow2_f32(exponent: i16) -> f32 {
+    if exponent >= -126 {
+        f32::from_bits(((exponent + 127) as u32) << 23)
+    } else {
+        let lo = f32::from_bits(1u32 << 23); // 2^-126
+        lo * pow2_f32(exponent + 126)
     }
 }
 
@@ -194,3 +392,75 @@ impl_generate_rand_ifromu!{i32, u32}
 impl_generate_rand_ifromu!{i64, u64}
 #[cfg(feature = "u128")]
 impl_generate_rand_ifromu!{i128, u128}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fast::FastRng;
+
+    #[test]
+    fn test_gen_range() {
+        let mut rng = FastRng::seed(1, 2);
+        for _ in 0..100 {
+            let n: u32 = rng.gen_range(1..7);
+            assert!(n >= 1 && n < 7);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample an empty range")]
+    fn test_gen_range_empty_panics() {
+        let mut rng = FastRng::seed(1, 2);
+        let _: u32 = rng.gen_range(5..5);
+    }
+
+    #[test]
+    fn test_gen_range_inclusive_full_range() {
+        let mut rng = FastRng::seed(1, 2);
+        // `0..=u32::max_value()` is the "whole type" sentinel case, not an empty range.
+        let _: u32 = rng.gen_range_inclusive(0..=u32::max_value());
+    }
+
+    #[test]
+    fn test_shuffle_is_a_permutation() {
+        let mut rng = FastRng::seed(1, 2);
+        let mut slice = [0, 1, 2, 3, 4, 5, 6, 7];
+        rng.shuffle(&mut slice);
+        let mut sorted = slice;
+        sorted.sort();
+        assert_eq!(sorted, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_choose_empty_is_none() {
+        let mut rng = FastRng::seed(1, 2);
+        let slice: [u32; 0] = [];
+        assert_eq!(rng.choose(&slice), None);
+    }
+
+    #[test]
+    fn test_choose_returns_an_element() {
+        let mut rng = FastRng::seed(1, 2);
+        let slice = [10, 20, 30];
+        for _ in 0..20 {
+            let chosen = rng.choose(&slice).unwrap();
+            assert!(slice.contains(chosen));
+        }
+    }
+
+    #[test]
+    fn test_choose_iter_empty_is_none() {
+        let mut rng = FastRng::seed(1, 2);
+        assert_eq!(rng.choose_iter(core::iter::empty::<u32>()), None);
+    }
+
+    #[test]
+    fn test_choose_iter_returns_an_element() {
+        let mut rng = FastRng::seed(1, 2);
+        let items = [10, 20, 30];
+        for _ in 0..20 {
+            let chosen = rng.choose_iter(items.iter().cloned()).unwrap();
+            assert!(items.contains(&chosen));
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! # Reseeding
+//!
+//! Wraps a generator so it periodically refreshes its internal state from a fresh seed,
+//! instead of running forever on the state it was constructed with. <br>
+//! Useful when wrapping a fast non-cryptographic core but wanting some forward-secrecy-ish
+//! behavior, or when a `Src` draws from a higher-quality (e.g. OS) source.
+
+use core::mem;
+use {Error, Random};
+
+/// Implemented by generators that expose a `seed(u64, u64) -> Self` constructor, so they
+/// can be dropped into a [`ReseedingRng`](struct.ReseedingRng.html).
+pub trait Seedable: Sized {
+    /// Builds a fresh instance from a seed and a sequence/stream constant, mirroring the
+    /// generator's own `seed` constructor.
+    fn seed(seed: u64, seq: u64) -> Self;
+}
+
+/// A `Random` adapter that delegates to an inner generator `R`, periodically replacing it
+/// with a freshly-seeded instance once `threshold` bytes have been produced. <br>
+/// The new seed is drawn from `Src`, which is typically a higher-quality (and possibly
+/// fallible) source such as the OS RNG.
+pub struct ReseedingRng<R, Src> {
+    inner: R,
+    source: Src,
+    threshold: u64,
+    produced: u64,
+}
+
+impl<R: Random + Seedable, Src: Random> ReseedingRng<R, Src> {
+    /// Creates a new `ReseedingRng` wrapping `inner`, reseeding it from `source` every
+    /// `threshold` bytes produced.
+    pub fn new(inner: R, threshold: u64, source: Src) -> Self {
+        ReseedingRng { inner, source, threshold, produced: 0 }
+    }
+
+    fn reseed(&mut self) -> Result<(), Error> {
+        let mut buf = [0u8; 16];
+        self.source.try_fill_bytes(&mut buf)?;
+        let mut seed_bytes = [0u8; 8];
+        let mut seq_bytes = [0u8; 8];
+        seed_bytes.copy_from_slice(&buf[..8]);
+        seq_bytes.copy_from_slice(&buf[8..]);
+        let seed: u64 = unsafe { mem::transmute(seed_bytes) };
+        let seq: u64 = unsafe { mem::transmute(seq_bytes) };
+        self.inner = R::seed(seed, seq);
+        self.produced = 0;
+        Ok(())
+    }
+}
+
+impl<R: Random + Seedable, Src: Random> Random for ReseedingRng<R, Src> {
+    fn try_fill_bytes(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        if self.produced.saturating_add(buf.len() as u64) > self.threshold {
+            self.reseed()?;
+        }
+        self.produced += buf.len() as u64;
+        self.inner.fill_bytes(buf);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fast::FastRng;
+
+    #[test]
+    fn test_reseeding_is_deterministic() {
+        let mut a = ReseedingRng::new(FastRng::seed(1, 2), 8, FastRng::seed(3, 4));
+        let mut b = ReseedingRng::new(FastRng::seed(1, 2), 8, FastRng::seed(3, 4));
+        for _ in 0..10 {
+            assert_eq!(a.get_u32(), b.get_u32());
+        }
+    }
+
+    #[test]
+    fn test_reseeding_changes_the_output_stream() {
+        // With `threshold == 0` every draw reseeds first, so the output must diverge from
+        // the same inner generator run without any reseeding at all.
+        let mut reseeding = ReseedingRng::new(FastRng::seed(1, 2), 0, FastRng::seed(3, 4));
+        let mut plain = FastRng::seed(1, 2);
+        let mut diverged = false;
+        for _ in 0..10 {
+            if reseeding.get_u32() != plain.get_u32() {
+                diverged = true;
+            }
+        }
+        assert!(diverged);
+    }
+}
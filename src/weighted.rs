@@ -0,0 +1,132 @@
+//! # Weighted sampling
+//!
+//! Implements Vose's alias method: after an O(n) one-time build from a weight list,
+//! sampling a weighted-random index costs O(1) and needs only one extra `u32` draw. <br>
+//! Probabilities are stored as fixed-point fractions of `2^32`, so no floating point is required.
+//! Requires the `alloc` feature for the `Vec`-backed alias tables.
+
+use alloc::vec::Vec;
+use Random;
+
+/// Samples indices in `0..len()` with probability proportional to the weights it was built from.
+pub struct WeightedIndex {
+    // prob[i] is the fixed-point (scaled by 2^32) chance of keeping outcome `i`, else `alias[i]` is returned.
+    prob: Vec<u32>,
+    alias: Vec<u32>,
+}
+
+impl WeightedIndex {
+    /// Builds a new `WeightedIndex` from integer `weights` using Vose's alias method.
+    ///
+    /// # Panics
+    /// Panics if `weights` is empty or every weight is zero.
+    pub fn new(weights: &[u32]) -> Self {
+        let n = weights.len();
+        assert!(n > 0, "WeightedIndex needs at least one weight");
+        let total: u64 = weights.iter().map(|&w| u64::from(w)).sum();
+        assert!(total > 0, "WeightedIndex needs at least one non-zero weight");
+
+        // Scale each weight to p_i = w_i * n / total, kept unscaled (as a fraction of `total`) for now.
+        let mut scaled: Vec<u64> = weights.iter().map(|&w| u64::from(w) * n as u64).collect();
+
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+        for (i, &p) in scaled.iter().enumerate() {
+            if p < total {
+                small.push(i);
+            } else {
+                large.push(i);
+            }
+        }
+
+        let mut prob = alloc::vec![0u32; n];
+        let mut alias = alloc::vec![0u32; n];
+
+        while !small.is_empty() && !large.is_empty() {
+            let l = small.pop().unwrap();
+            let g = large.pop().unwrap();
+            prob[l] = div_scaled_shl32(scaled[l], total);
+            alias[l] = g as u32;
+            scaled[g] = scaled[g] + scaled[l] - total;
+            if scaled[g] < total {
+                small.push(g);
+            } else {
+                large.push(g);
+            }
+        }
+        // Leftover columns (rounding only) always keep their own outcome.
+        for l in small.into_iter().chain(large) {
+            prob[l] = u32::max_value();
+            alias[l] = l as u32;
+        }
+
+        WeightedIndex { prob, alias }
+    }
+
+    /// Draws a weighted-random index in `0..len()`.
+    pub fn sample<R: Random + ?Sized>(&self, rand: &mut R) -> usize {
+        let i = rand.gen_range_u32(self.prob.len() as u32) as usize;
+        let coin = rand.get_u32();
+        if coin < self.prob[i] {
+            i
+        } else {
+            self.alias[i] as usize
+        }
+    }
+
+    /// The number of weights this table was built from.
+    pub fn len(&self) -> usize {
+        self.prob.len()
+    }
+
+    /// Returns `true` if this table has no weights. `new` always panics on empty input,
+    /// so this is only ever `false`.
+    pub fn is_empty(&self) -> bool {
+        self.prob.is_empty()
+    }
+}
+
+/// Computes `floor((numerator << 32) / denominator) as u32`, assuming `numerator < denominator`
+/// (so the result fits in a `u32`). `numerator << 32` alone can overflow `u64` whenever
+/// `numerator >= 2^32`, so this widens to `u128` before shifting.
+#[cfg(feature = "u128")]
+fn div_scaled_shl32(numerator: u64, denominator: u64) -> u32 {
+    ((u128::from(numerator) << 32) / u128::from(denominator)) as u32
+}
+
+/// Computes `floor((numerator << 32) / denominator) as u32` without a native 128-bit type,
+/// assuming `numerator < denominator` (so the result fits in a `u32`).
+#[cfg(not(feature = "u128"))]
+fn div_scaled_shl32(numerator: u64, denominator: u64) -> u32 {
+    let mut remainder: u64 = numerator;
+    let mut quotient: u32 = 0;
+    for _ in 0..32 {
+        let carry = remainder >> 63;
+        remainder <<= 1;
+        quotient <<= 1;
+        if carry != 0 || remainder >= denominator {
+            remainder = remainder.wrapping_sub(denominator);
+            quotient |= 1;
+        }
+    }
+    quotient
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_weights_dont_overflow() {
+        // Each of these weights alone scales (by `n = 5`) past `2^32`, which used to
+        // overflow the fixed-point probability calculation silently.
+        let weights = [900_000_000, 900_000_000, 900_000_000, 900_000_000, 900_000_001];
+        let w = WeightedIndex::new(&weights);
+        // All four equal, under-total weights should end up with a near-certain
+        // probability of keeping their own outcome, not the tiny garbage value an
+        // overflowing `u64` shift would have produced.
+        for i in 0..4 {
+            assert!(w.prob[i] > u32::max_value() - 10);
+        }
+    }
+}